@@ -0,0 +1,290 @@
+use crate::traits::{self, EmitResult};
+use std::{borrow::Cow, cell::RefCell, collections::VecDeque, rc::Rc};
+
+struct InnerRef<'parent, Tin, Tout> {
+    inq: &'parent mut VecDeque<Tin>,
+    outq: &'parent mut VecDeque<Tout>,
+}
+
+/// Reports whether a buffered emit had to drop the oldest event to stay
+/// within capacity, returned by [`Queue::try_emit`]/[`Secondary::try_emit`]
+/// so callers can detect backpressure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// The event was stored without dropping anything.
+    Delivered,
+    /// The event was stored, but the oldest buffered event was dropped to
+    /// make room for it; on a zero-capacity queue, the event itself was
+    /// dropped without being stored.
+    Overflowed,
+}
+
+fn push_bounded<T, const N: usize>(queue: &mut VecDeque<T>, event: T) -> Overflow {
+    // A zero-capacity queue stores nothing; without this guard
+    // `queue.len() >= N` is trivially true and the pop-then-push below
+    // would behave like capacity 1 instead of capacity 0.
+    if N == 0 {
+        return Overflow::Overflowed;
+    }
+
+    let overflow = if queue.len() >= N {
+        queue.pop_front();
+        Overflow::Overflowed
+    } else {
+        Overflow::Delivered
+    };
+    queue.push_back(event);
+    overflow
+}
+
+/// Non-thread-safe, reference-counted, bidirectional event queue that
+/// retains up to `N` buffered events per direction instead of only the
+/// latest one.
+///
+/// This is the bounded-ring-buffer sibling of
+/// [`bidir_single::Queue`](crate::bidir_single::Queue): where that type
+/// silently drops the previous event on every `emit`, this one keeps the
+/// last `N` events per direction, delivering them in order and dropping
+/// the oldest once capacity is exceeded.
+///
+/// The first type parameter describes the events which the primary peer
+/// receives, the second type parameter describes the events which the
+/// secondary peer receives.
+#[derive(Clone, Debug)]
+pub struct Queue<Tp, Ts, const N: usize>(pub(crate) Rc<RefCell<(VecDeque<Tp>, VecDeque<Ts>)>>);
+
+/// The "other" end of the bidirectional [`Queue`](crate::bidir_buffered::Queue)
+#[derive(Clone, Debug)]
+pub struct Secondary<Tp, Ts, const N: usize>(Queue<Tp, Ts, N>);
+
+impl<Tp, Ts, const N: usize> Default for Queue<Tp, Ts, N> {
+    fn default() -> Self {
+        Queue(Rc::new(RefCell::new((VecDeque::new(), VecDeque::new()))))
+    }
+}
+
+impl<Tp, Ts, const N: usize> Queue<Tp, Ts, N> {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// This function returns the "other" end of the bidirectional `Queue`
+    ///
+    /// NOTE: multiple calls to this method on the same queue
+    /// return wrapped references to the same [`Secondary`](crate::bidir_buffered::Secondary).
+    #[inline]
+    pub fn secondary(&self) -> Secondary<Tp, Ts, N> {
+        Secondary(Queue(Rc::clone(&self.0)))
+    }
+
+    fn on_queues_mut<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(InnerRef<'_, Tp, Ts>) -> R,
+    {
+        let inner = &mut *self.0.borrow_mut();
+        f(InnerRef { inq: &mut inner.0, outq: &mut inner.1 })
+    }
+}
+
+impl<Tp, Ts, const N: usize> Secondary<Tp, Ts, N> {
+    fn on_queues_mut<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(InnerRef<'_, Ts, Tp>) -> R,
+    {
+        let inner = &mut *(self.0).0.borrow_mut();
+        f(InnerRef { inq: &mut inner.1, outq: &mut inner.0 })
+    }
+}
+
+macro_rules! impl_queue_part {
+    ($strucn:ident, $tp1:ident, $tp2:ident, $tin:ident, $tout:ident) => {
+        impl<$tp1, $tp2, const N: usize> $strucn<$tp1, $tp2, N> {
+            /// This function iterates over every currently buffered
+            /// inbound event and optionally schedules a reply per event
+            /// into the outgoing event queue.
+            #[inline]
+            pub fn bounce<F>(&self, mut f: F)
+            where
+                F: FnMut($tin) -> Option<$tout>,
+            {
+                self.on_queues_mut(|x| {
+                    let pending: Vec<_> = x.inq.drain(..).collect();
+                    for event in pending {
+                        if let Some(reply) = f(event) {
+                            push_bounded::<$tout, N>(x.outq, reply);
+                        }
+                    }
+                })
+            }
+
+            /// This function retrieves every currently buffered inbound
+            /// event, oldest first, clearing the inbound buffer.
+            #[inline]
+            pub fn retrieve_all(&self) -> Vec<$tin> {
+                self.on_queues_mut(|x| x.inq.drain(..).collect())
+            }
+
+            /// Emits `event`, reporting via [`Overflow`] whether the
+            /// oldest buffered event had to be dropped to make room for
+            /// it.
+            ///
+            /// This is the capacity-aware counterpart to the
+            /// [`Emitter`](traits::Emitter) impl's `emit`: that impl is
+            /// infallible by trait contract, so on overflow it drops the
+            /// oldest buffered event *silently* — callers that need to
+            /// detect that data loss must go through `try_emit` instead.
+            #[inline]
+            pub fn try_emit(&self, event: $tout) -> Overflow {
+                self.on_queues_mut(|x| push_bounded::<$tout, N>(x.outq, event))
+            }
+        }
+
+        impl<$tp1, $tp2, const N: usize> traits::QueueInterfaceCommon for $strucn<$tp1, $tp2, N> {
+            type Item = $tout;
+
+            #[inline]
+            fn buffer_is_empty(&self) -> bool {
+                self.on_queues_mut(|x| x.outq.is_empty())
+            }
+        }
+
+        impl<$tin, $tout: Clone, const N: usize> traits::Emitter for $strucn<$tp1, $tp2, N> {
+            /// NOTE: on overflow this silently drops the oldest buffered
+            /// event and still reports [`EmitResult::Delivered`] — the
+            /// shared `Emitter` trait has no variant for "delivered, but
+            /// something else was dropped". Callers that must observe
+            /// backpressure should call `try_emit` directly instead of
+            /// going through this trait.
+            #[inline]
+            fn emit<'a>(&self, event: Cow<'a, $tout>) -> EmitResult<'a, $tout> {
+                self.on_queues_mut(|x| {
+                    push_bounded::<$tout, N>(x.outq, event.into_owned());
+                });
+                EmitResult::Delivered
+            }
+        }
+
+        impl<$tin: Clone, $tout, const N: usize> traits::Listen for $strucn<$tp1, $tp2, N> {
+            type Item = $tin;
+
+            #[inline]
+            fn with<F, R>(&self, f: F) -> R
+            where
+                F: FnOnce(&[Self::Item]) -> R,
+            {
+                f(&self.peek()[..])
+            }
+
+            #[inline]
+            fn map<F, R>(&self, f: F) -> Vec<R>
+            where
+                F: FnMut(&Self::Item) -> R,
+            {
+                self.on_queues_mut(|x| x.inq.drain(..).map(f).collect())
+            }
+
+            #[inline]
+            fn peek(&self) -> Vec<Self::Item> {
+                self.on_queues_mut(|x| x.inq.drain(..).collect())
+            }
+
+            #[inline]
+            fn with_n<F, R>(&self, n: usize, f: F) -> R
+            where
+                F: FnOnce(&[Self::Item]) -> R,
+            {
+                f(&self.peek_n(n)[..])
+            }
+
+            #[inline]
+            fn map_n<F, R>(&self, n: usize, f: F) -> Vec<R>
+            where
+                F: FnMut(&Self::Item) -> R,
+            {
+                if n == 0 {
+                    Vec::new()
+                } else {
+                    self.on_queues_mut(|x| {
+                        let take = n.min(x.inq.len());
+                        x.inq.drain(..take).map(f).collect()
+                    })
+                }
+            }
+
+            #[inline]
+            fn peek_n(&self, n: usize) -> Vec<Self::Item> {
+                if n == 0 {
+                    Vec::new()
+                } else {
+                    self.on_queues_mut(|x| {
+                        let take = n.min(x.inq.len());
+                        x.inq.drain(..take).collect()
+                    })
+                }
+            }
+        }
+    };
+}
+
+impl_queue_part!(Queue, Tp, Ts, Tp, Ts);
+impl_queue_part!(Secondary, Tp, Ts, Ts, Tp);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_bidir_buffered_evq() {
+        let primary = super::Queue::<_, _, 3>::new();
+        let secondary = primary.secondary();
+
+        primary.emit_owned(1);
+        primary.emit_owned(2);
+        primary.emit_owned(3);
+        assert_eq!(secondary.peek(), &[1, 2, 3]);
+
+        secondary.emit_owned(4);
+        secondary.emit_owned(5);
+        secondary.emit_owned(6);
+
+        primary.bounce(|x| Some(x + 1));
+        assert_eq!(secondary.peek(), &[5, 6, 7]);
+    }
+
+    #[test]
+    fn test_n_bidir_buffered_evq() {
+        let primary = super::Queue::<_, _, 3>::new();
+        let secondary = primary.secondary();
+
+        primary.emit_owned(1);
+        primary.emit_owned(2);
+        primary.emit_owned(3);
+        assert_eq!(secondary.peek_n(0), &[]);
+        assert_eq!(secondary.peek_n(2), &[1, 2]);
+        assert_eq!(secondary.peek_n(3), &[3]);
+    }
+
+    #[test]
+    fn test_bidir_buffered_overflow() {
+        let primary = super::Queue::<i32, i32, 2>::new();
+
+        assert_eq!(primary.try_emit(1), super::Overflow::Delivered);
+        assert_eq!(primary.try_emit(2), super::Overflow::Delivered);
+        assert_eq!(primary.try_emit(3), super::Overflow::Overflowed);
+
+        let secondary = primary.secondary();
+        assert_eq!(secondary.peek(), &[2, 3]);
+    }
+
+    #[test]
+    fn test_bidir_buffered_zero_capacity_stores_nothing() {
+        let primary = super::Queue::<i32, i32, 0>::new();
+
+        assert_eq!(primary.try_emit(1), super::Overflow::Overflowed);
+        assert_eq!(primary.try_emit(2), super::Overflow::Overflowed);
+
+        let secondary = primary.secondary();
+        assert_eq!(secondary.peek(), &[]);
+    }
+}
@@ -0,0 +1,20 @@
+//! Lightweight, composable event queues and listeners for reactive,
+//! single-threaded programs.
+//!
+//! Several interchangeable queue flavors live alongside each other as
+//! modules:
+//!
+//! - [`nonrc`] — a single-type event queue with independent listeners.
+//! - [`bidir_single`] — a 1:1 bidirectional queue that only ever retains
+//!   the latest event per direction.
+//! - [`bidir_buffered`] — the bounded-ring-buffer sibling of
+//!   [`bidir_single`], retaining up to a fixed number of events per
+//!   direction.
+//! - [`reactor`] — a heterogeneous, type-keyed event bus with cascading
+//!   dispatch.
+
+pub mod bidir_buffered;
+pub mod bidir_single;
+pub mod nonrc;
+
+pub mod reactor;
@@ -3,14 +3,27 @@ use crate::{
     *,
 };
 use std::cell::RefCell;
+#[cfg(feature = "async")]
+use std::{collections::HashMap, task::Waker};
 
 #[derive(Debug)]
-pub struct Queue<T>(RefCell<RawEventQueue<T>>);
+pub struct Queue<T>(
+    RefCell<RawEventQueue<T>>,
+    // A listener can have more than one outstanding future/stream parked
+    // on it at once (e.g. two `next()` calls, or `next()` alongside a
+    // `Stream`), so each key maps to every waker currently parked under
+    // it, not just the most recent one.
+    #[cfg(feature = "async")] RefCell<HashMap<ListenerKey, Vec<Waker>>>,
+);
 
 impl<T> Default for Queue<T> {
     #[inline]
     fn default() -> Self {
-        Self(RefCell::new(RawEventQueue::new()))
+        Self(
+            RefCell::new(RawEventQueue::new()),
+            #[cfg(feature = "async")]
+            RefCell::new(HashMap::new()),
+        )
     }
 }
 
@@ -37,7 +50,10 @@ impl<T> private::QueueInterface<T> for Queue<T> {
 impl<T> GenericQueueInterface<T> for Queue<T> {
     #[inline]
     fn push(&self, event: T) -> bool {
-        self.with_inner_mut(|inner| inner.push(event))
+        let pushed = self.with_inner_mut(|inner| inner.push(event));
+        #[cfg(feature = "async")]
+        self.wake_parked();
+        pushed
     }
 
     #[inline]
@@ -62,7 +78,7 @@ impl<T> Queue<T> {
 
     #[inline]
     pub fn listen(&self) -> Listener<'_, T> {
-        Listener::new(&self.0)
+        Listener::new(self)
     }
 
     #[cfg(test)]
@@ -70,17 +86,67 @@ impl<T> Queue<T> {
     fn event_len(&self) -> usize {
         self.with_inner(|inner| inner.events.len())
     }
+
+    #[cfg(all(test, feature = "async"))]
+    #[inline]
+    fn parked_waker_count(&self) -> usize {
+        self.1.borrow().values().map(Vec::len).sum()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Queue<T> {
+    /// Parks `waker` under `key`, alongside any wakers already parked by
+    /// *other* outstanding futures/streams on the same listener, so all
+    /// of them are woken rather than the earlier ones being silently
+    /// dropped.
+    ///
+    /// If a waker already parked under `key` would wake the same task
+    /// (per [`Waker::will_wake`]), it's replaced in place instead of
+    /// appended — otherwise re-polling the same still-pending future
+    /// (the normal case in a `select!`/loop-driven consumer) would park a
+    /// fresh clone on every poll and accumulate duplicates forever.
+    fn park_waker(&self, key: ListenerKey, waker: Waker) {
+        let mut parked = self.1.borrow_mut();
+        let wakers = parked.entry(key).or_default();
+        match wakers.iter_mut().find(|parked| parked.will_wake(&waker)) {
+            Some(slot) => *slot = waker,
+            None => wakers.push(waker),
+        }
+    }
+
+    /// Removes every waker parked under `key` without waking them, called
+    /// when the owning [`Listener`] is dropped so pending futures don't
+    /// leak wakers.
+    fn forget_waker(&self, key: ListenerKey) {
+        self.1.borrow_mut().remove(&key);
+    }
+
+    /// Wakes and forgets every currently parked waker.
+    ///
+    /// Every listener is woken on every push rather than only the ones
+    /// whose position actually advanced, since the queue doesn't track
+    /// per-listener readiness outside of `RawEventQueue` itself; a
+    /// spuriously woken future simply observes an empty buffer and parks
+    /// again, which is within the `Future`/`Stream` contract.
+    fn wake_parked(&self) {
+        for (_, wakers) in self.1.borrow_mut().drain() {
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
-pub struct Listener<'parent, T>(ListenerKey, &'parent RefCell<RawEventQueue<T>>);
+pub struct Listener<'parent, T>(ListenerKey, &'parent Queue<T>);
 
 impl<T> private::Listen<T> for Listener<'_, T> {
     fn with_inner_mut<F, R>(&self, f: F) -> Option<R>
     where
         F: FnOnce(crate::intern::ListenerKey, &mut RawEventQueue<T>) -> R,
     {
-        let mut inner = self.1.borrow_mut();
+        let mut inner = self.1 .0.borrow_mut();
         Some(f(self.0, &mut inner))
     }
 }
@@ -88,15 +154,85 @@ impl<T> private::Listen<T> for Listener<'_, T> {
 impl<T> Drop for Listener<'_, T> {
     fn drop(&mut self) {
         self.with_inner_mut(|key, ev| ev.remove_listener(key));
+        #[cfg(feature = "async")]
+        self.1.forget_waker(self.0);
     }
 }
 
 impl<'a, T> Listener<'a, T> {
-    fn new(parent: &'a RefCell<RawEventQueue<T>>) -> Self {
-        Listener(parent.borrow_mut().create_listener(), parent)
+    fn new(parent: &'a Queue<T>) -> Self {
+        Listener(parent.0.borrow_mut().create_listener(), parent)
     }
 }
 
+#[cfg(feature = "async")]
+mod future {
+    use super::*;
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    /// A [`Future`] that resolves to the next event observed by a
+    /// [`Listener`], returned by [`Listener::next`].
+    pub struct Next<'a, 'parent, T>(&'a Listener<'parent, T>);
+
+    impl<'parent, T> Listener<'parent, T> {
+        /// Returns a future that resolves to the next event observed by
+        /// this listener, parking the polling task's waker if the
+        /// listener's buffer is currently empty.
+        #[inline]
+        pub fn next(&self) -> Next<'_, 'parent, T> {
+            Next(self)
+        }
+
+        /// Adapts this listener into a [`futures::Stream`] that yields
+        /// every event it observes from this point on.
+        #[inline]
+        pub fn into_stream(self) -> EventStream<'parent, T> {
+            EventStream(self)
+        }
+    }
+
+    impl<T> Future for Next<'_, '_, T> {
+        type Output = T;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let mut pulled = self.0.peek_n(1);
+            match pulled.pop() {
+                Some(event) => Poll::Ready(event),
+                None => {
+                    self.0 .1.park_waker(self.0 .0, cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// A [`futures::Stream`] adapter over a [`Listener`], returned by
+    /// [`Listener::into_stream`].
+    pub struct EventStream<'parent, T>(Listener<'parent, T>);
+
+    impl<T> futures::Stream for EventStream<'_, T> {
+        type Item = T;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let mut pulled = self.0.peek_n(1);
+            match pulled.pop() {
+                Some(event) => Poll::Ready(Some(event)),
+                None => {
+                    self.0 .1.park_waker(self.0 .0, cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub use future::{EventStream, Next};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +286,94 @@ mod tests {
 
         assert_eq!(event.event_len(), 0);
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_event_listener_next() {
+        let event = Queue::new();
+        let listener = event.listen();
+
+        event.push(1i32);
+
+        let first = futures::executor::block_on(listener.next());
+        assert_eq!(first, 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_event_listener_stream() {
+        use futures::StreamExt;
+
+        let event = Queue::new();
+        let listener = event.listen();
+
+        event.push(1i32);
+        event.push(2i32);
+
+        let mut stream = listener.into_stream();
+        let first = futures::executor::block_on(stream.next());
+        let second = futures::executor::block_on(stream.next());
+
+        assert_eq!(first, Some(1));
+        assert_eq!(second, Some(2));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_event_listener_concurrent_futures_both_parked() {
+        use futures::task::{waker, ArcWake};
+        use std::{future::Future, sync::Arc, task::Context};
+
+        struct NoopWake;
+
+        impl ArcWake for NoopWake {
+            fn wake_by_ref(_arc_self: &Arc<Self>) {}
+        }
+
+        let event = Queue::new();
+        let listener = event.listen();
+
+        let mut fut_1 = Box::pin(listener.next());
+        let mut fut_2 = Box::pin(listener.next());
+
+        // Each future is polled from its own task, so neither waker
+        // `will_wake` the other.
+        let waker_1 = waker(Arc::new(NoopWake));
+        let waker_2 = waker(Arc::new(NoopWake));
+        let mut cx_1 = Context::from_waker(&waker_1);
+        let mut cx_2 = Context::from_waker(&waker_2);
+
+        assert!(fut_1.as_mut().poll(&mut cx_1).is_pending());
+        assert!(fut_2.as_mut().poll(&mut cx_2).is_pending());
+
+        // Both futures must still have a waker parked under the shared
+        // listener key; the second `poll` must not have evicted the
+        // first's waker, or that future would be left parked forever
+        // once an event actually arrives.
+        assert_eq!(event.parked_waker_count(), 2);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_event_listener_repolling_same_future_does_not_duplicate_waker() {
+        use futures::task::noop_waker;
+        use std::{future::Future, task::Context};
+
+        let event = Queue::new();
+        let listener = event.listen();
+
+        let mut fut = Box::pin(listener.next());
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        for _ in 0..5 {
+            assert!(fut.as_mut().poll(&mut cx).is_pending());
+        }
+
+        // Re-polling the same still-pending future over and over (the
+        // normal shape of a `select!`/loop-driven consumer) must not
+        // accumulate a fresh waker clone on every poll.
+        assert_eq!(event.parked_waker_count(), 1);
+    }
 }
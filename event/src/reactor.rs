@@ -0,0 +1,239 @@
+//! A heterogeneous, type-keyed event bus.
+//!
+//! Unlike [`nonrc::Queue`](crate::nonrc::Queue) or
+//! [`bidir_single::Queue`](crate::bidir_single::Queue), which are
+//! monomorphized over a single event type, a [`Reactor`] lets handlers be
+//! registered against the *concrete type* of the event they care about and
+//! dispatches synchronously to every handler registered for that type.
+
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+};
+
+/// Identifies a handler registered with a [`Reactor`].
+///
+/// Returned by [`Reactor::listen`] and accepted by
+/// [`Reactor::remove_listener`], mirroring the role that `ListenerKey`
+/// plays for `RawEventQueue`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ListenerKey(u64);
+
+/// The context handed to every handler invoked by a [`Reactor`].
+///
+/// Calling [`send`](ReactorCtx::send) from within a handler doesn't
+/// dispatch immediately; it queues the event to be dispatched once the
+/// current batch of handlers has finished running, so a handler can
+/// safely chain-fire further events without re-entering the reactor's
+/// borrowed state.
+pub struct ReactorCtx<'a> {
+    deferred: &'a RefCell<VecDeque<Deferred>>,
+}
+
+impl ReactorCtx<'_> {
+    /// Queues `event` for dispatch after the current batch of handlers
+    /// returns.
+    #[inline]
+    pub fn send<E: 'static>(&self, event: E) {
+        self.deferred.borrow_mut().push_back(Deferred::Send(Box::new(event)));
+    }
+
+    /// Defers removal of the handler identified by `key` until after the
+    /// current batch of handlers finishes running.
+    #[inline]
+    pub fn remove_listener(&self, key: ListenerKey) {
+        self.deferred.borrow_mut().push_back(Deferred::Remove(key));
+    }
+}
+
+enum Deferred {
+    Send(Box<dyn Any>),
+    Remove(ListenerKey),
+}
+
+type Handler = Box<dyn for<'ctx> FnMut(&dyn Any, &mut ReactorCtx<'ctx>)>;
+
+struct Entry {
+    key: ListenerKey,
+    handler: Handler,
+}
+
+/// A heterogeneous, type-keyed event bus with cascading dispatch.
+///
+/// Handlers are registered with [`listen`](Reactor::listen) against a
+/// concrete event type `E` and invoked synchronously whenever an event of
+/// that type is dispatched through [`send`](Reactor::send). A handler may
+/// itself fire further events through the [`ReactorCtx`] it's given;
+/// those are queued and drained breadth-first after the current batch of
+/// handlers returns, rather than being dispatched by recursing back into
+/// the reactor, which would otherwise double-borrow its internal state.
+#[derive(Default)]
+pub struct Reactor {
+    handlers: RefCell<BTreeMap<TypeId, Vec<Entry>>>,
+    deferred: RefCell<VecDeque<Deferred>>,
+    next_key: RefCell<u64>,
+}
+
+impl Reactor {
+    /// Creates an empty reactor.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers `handler` to be invoked with every `E` dispatched via
+    /// [`send`](Reactor::send).
+    ///
+    /// Returns a key that can later be passed to
+    /// [`remove_listener`](Reactor::remove_listener) to unregister it.
+    pub fn listen<E, F>(&self, mut handler: F) -> ListenerKey
+    where
+        E: 'static,
+        F: FnMut(&E, &mut ReactorCtx<'_>) + 'static,
+    {
+        let key = self.alloc_key();
+
+        let wrapped: Handler = Box::new(move |event, ctx| {
+            if let Some(event) = event.downcast_ref::<E>() {
+                handler(event, ctx);
+            }
+        });
+
+        self.handlers.borrow_mut().entry(TypeId::of::<E>()).or_default().push(Entry {
+            key,
+            handler: wrapped,
+        });
+
+        key
+    }
+
+    /// Dispatches `event` synchronously to every handler registered for
+    /// `E`, then drains any further events or listener removals queued by
+    /// those handlers (breadth-first) before returning.
+    pub fn send<E: 'static>(&self, event: E) {
+        self.dispatch(TypeId::of::<E>(), &event);
+        self.drain();
+    }
+
+    /// Unregisters the handler identified by `key`.
+    ///
+    /// This is safe to call from within a handler: since the handler map
+    /// is already borrowed while dispatching, the removal is deferred
+    /// until the current batch finishes running, same as
+    /// [`ReactorCtx::remove_listener`].
+    pub fn remove_listener(&self, key: ListenerKey) {
+        match self.handlers.try_borrow_mut() {
+            Ok(mut handlers) => Self::apply_removal(&mut handlers, key),
+            Err(_) => self.deferred.borrow_mut().push_back(Deferred::Remove(key)),
+        }
+    }
+
+    fn alloc_key(&self) -> ListenerKey {
+        let mut next_key = self.next_key.borrow_mut();
+        let key = ListenerKey(*next_key);
+        *next_key += 1;
+        key
+    }
+
+    fn apply_removal(handlers: &mut BTreeMap<TypeId, Vec<Entry>>, key: ListenerKey) {
+        for entries in handlers.values_mut() {
+            entries.retain(|entry| entry.key != key);
+        }
+    }
+
+    fn dispatch(&self, type_id: TypeId, event: &dyn Any) {
+        let mut ctx = ReactorCtx { deferred: &self.deferred };
+        let mut handlers = self.handlers.borrow_mut();
+        if let Some(entries) = handlers.get_mut(&type_id) {
+            for entry in entries.iter_mut() {
+                (entry.handler)(event, &mut ctx);
+            }
+        }
+    }
+
+    fn drain(&self) {
+        loop {
+            let next = self.deferred.borrow_mut().pop_front();
+            match next {
+                Some(Deferred::Send(boxed)) => {
+                    let type_id = boxed.as_ref().type_id();
+                    self.dispatch(type_id, boxed.as_ref());
+                }
+                Some(Deferred::Remove(key)) => {
+                    Self::apply_removal(&mut self.handlers.borrow_mut(), key);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Debug, PartialEq)]
+    struct Ping(i32);
+
+    #[derive(Debug, PartialEq)]
+    struct Pong(i32);
+
+    #[test]
+    fn test_reactor_dispatch() {
+        let reactor = Reactor::new();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let seen_clone = Rc::clone(&seen);
+        reactor.listen::<Ping, _>(move |ev, _ctx| seen_clone.borrow_mut().push(ev.0));
+
+        reactor.send(Ping(1));
+        reactor.send(Ping(2));
+
+        assert_eq!(&*seen.borrow(), &[1, 2]);
+    }
+
+    #[test]
+    fn test_reactor_cascading_dispatch() {
+        let reactor = Reactor::new();
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let order_clone = Rc::clone(&order);
+        reactor.listen::<Ping, _>(move |ev, ctx| {
+            order_clone.borrow_mut().push(format!("ping:{}", ev.0));
+            ctx.send(Pong(ev.0));
+        });
+
+        let order_clone = Rc::clone(&order);
+        reactor.listen::<Pong, _>(move |ev, _ctx| {
+            order_clone.borrow_mut().push(format!("pong:{}", ev.0));
+        });
+
+        reactor.send(Ping(1));
+
+        assert_eq!(&*order.borrow(), &["ping:1", "pong:1"]);
+    }
+
+    #[test]
+    fn test_reactor_remove_listener_from_within_handler() {
+        let reactor = Reactor::new();
+        let calls = Rc::new(RefCell::new(0));
+        let own_key = Rc::new(RefCell::new(None));
+
+        let calls_clone = Rc::clone(&calls);
+        let own_key_clone = Rc::clone(&own_key);
+        let key = reactor.listen::<Ping, _>(move |_ev, ctx| {
+            *calls_clone.borrow_mut() += 1;
+            if let Some(key) = *own_key_clone.borrow() {
+                ctx.remove_listener(key);
+            }
+        });
+        *own_key.borrow_mut() = Some(key);
+
+        reactor.send(Ping(1));
+        reactor.send(Ping(2));
+
+        assert_eq!(*calls.borrow(), 1);
+    }
+}